@@ -1,60 +1,67 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use libp2p::core::Multiaddr;
+use libp2p::multiaddr::Protocol;
 use libp2p::PeerId;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::str::FromStr;
 use structopt::clap::AppSettings;
 use structopt::StructOpt;
+use url::Url;
 use uuid::Uuid;
 
-pub fn parse_args<I, T>(raw_args: I) -> Result<Arguments>
+pub fn parse_args<I, T>(raw_args: I) -> Result<ParseResult>
 where
     I: IntoIterator<Item = T>,
     T: Into<OsString> + Clone,
 {
-    let matches = RawArguments::clap()
+    let matches = match RawArguments::clap()
         .setting(AppSettings::SubcommandsNegateReqs)
         .setting(AppSettings::ArgsNegateSubcommands)
-        .get_matches_from_safe(raw_args)?;
+        .get_matches_from_safe(raw_args)
+    {
+        Ok(matches) => matches,
+        Err(e)
+            if e.kind == structopt::clap::ErrorKind::HelpDisplayed
+                || e.kind == structopt::clap::ErrorKind::VersionDisplayed =>
+        {
+            return Ok(ParseResult::Early(e.message));
+        }
+        Err(e) => return Err(e.into()),
+    };
 
-    Ok(if matches.subcommand_name().is_none() {
+    Ok(ParseResult::Arguments(if matches.subcommand_name().is_none() {
         let args = RawArguments::from_clap(&matches);
-        Arguments {
-            config: args.standard_opts.config,
-            debug: args.standard_opts.debug,
-            command: Command::BuyXmr {
-                receive_monero_address: args.receive_monero_address,
-                alice_peer_id: args.alice_connection.alice_peer_id,
-                alice_addr: args.alice_connection.alice_addr,
-            },
-        }
+        let resolved = args.standard_opts.resolve()?;
+        ensure_tor_available_for_onion_address(
+            &args.alice_connection.alice_addr,
+            resolved.tor_socks5_port,
+        )?;
+        resolved.into_arguments(Command::BuyXmr {
+            receive_monero_address: args.receive_monero_address,
+            alice_peer_id: args.alice_connection.alice_peer_id,
+            alice_addr: args.alice_connection.alice_addr,
+        })
     } else {
         let sub_command: SubCommand = SubCommand::from_clap(&matches);
         match sub_command {
-            SubCommand::History { debug } => Arguments {
-                config: None,
-                debug,
-                command: Command::History,
-            },
+            SubCommand::History { standard_opts } => {
+                standard_opts.resolve()?.into_arguments(Command::History)
+            }
             SubCommand::Cancel {
                 swap_id,
                 force,
-                standard_opts: StandardOpts { config, debug },
-            } => Arguments {
-                config,
-                debug,
-                command: Command::Cancel { swap_id, force },
-            },
+                standard_opts,
+            } => standard_opts
+                .resolve()?
+                .into_arguments(Command::Cancel { swap_id, force }),
             SubCommand::Refund {
                 swap_id,
                 force,
-                standard_opts: StandardOpts { config, debug },
-            } => Arguments {
-                config,
-                debug,
-                command: Command::Refund { swap_id, force },
-            },
+                standard_opts,
+            } => standard_opts
+                .resolve()?
+                .into_arguments(Command::Refund { swap_id, force }),
             SubCommand::Resume {
                 receive_monero_address,
                 swap_id,
@@ -63,28 +70,81 @@ where
                         alice_peer_id,
                         alice_addr,
                     },
-                standard_opts: StandardOpts { config, debug },
-            } => Arguments {
-                config,
-                debug,
-                command: Command::Resume {
+                standard_opts,
+            } => {
+                let resolved = standard_opts.resolve()?;
+                ensure_tor_available_for_onion_address(&alice_addr, resolved.tor_socks5_port)?;
+                resolved.into_arguments(Command::Resume {
                     receive_monero_address,
                     swap_id,
                     alice_peer_id,
                     alice_addr,
-                },
-            },
+                })
+            }
+            SubCommand::ListSellers {
+                rendezvous_point,
+                namespace,
+                standard_opts,
+            } => {
+                let resolved = standard_opts.resolve()?;
+                ensure_tor_available_for_onion_address(&rendezvous_point, resolved.tor_socks5_port)?;
+                let namespace = namespace.unwrap_or_else(|| XmrBtcNamespace::from_network(resolved.env_config));
+                resolved.into_arguments(Command::ListSellers {
+                    rendezvous_point,
+                    namespace,
+                })
+            }
         }
-    })
+    }))
+}
+
+/// Outcome of parsing the command line: either a fully resolved set of
+/// `Arguments` ready to drive a swap, or a message clap produced for a flag
+/// that needs no further execution (e.g. `--help`/`--version`). For
+/// `--help` clap does not print the help text itself on the
+/// `get_matches_from_safe` path used here, so the caller must print it
+/// before exiting; for `--version` clap has already written the version to
+/// stdout and the message is empty.
+#[derive(Debug, PartialEq)]
+pub enum ParseResult {
+    Arguments(Arguments),
+    Early(String),
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Arguments {
     pub config: Option<PathBuf>,
     pub debug: bool,
+    /// Switches command output to newline-delimited JSON instead of
+    /// human-readable text, e.g. `list_sellers::list_sellers` prints one
+    /// JSON object per seller instead of a table.
+    pub json: bool,
+    pub electrum_rpc_url: Url,
+    pub monero_daemon_address: String,
+    pub bitcoin_target_block: usize,
+    pub tor_socks5_port: Option<u16>,
+    pub env_config: Network,
+    pub data_dir: PathBuf,
     pub command: Command,
 }
 
+/// The network a swap runs on. Every network-dependent default (Electrum URL,
+/// Monero daemon, confirmation target, data directory) is derived from this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+impl Network {
+    fn data_dir_name(self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet",
+            Network::Testnet => "testnet",
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq)]
 pub enum Command {
@@ -108,6 +168,13 @@ pub enum Command {
         swap_id: Uuid,
         force: bool,
     },
+    /// Discover makers registered at a rendezvous point and print their peer
+    /// id, address and current quote instead of relying on a hardcoded maker.
+    /// Run via `list_sellers::list_sellers(rendezvous_point, namespace, json)`.
+    ListSellers {
+        rendezvous_point: Multiaddr,
+        namespace: XmrBtcNamespace,
+    },
 }
 
 #[derive(structopt::StructOpt, Debug)]
@@ -130,8 +197,8 @@ struct RawArguments {
 #[structopt(name = "xmr_btc-swap", about = "XMR BTC atomic swap")]
 enum SubCommand {
     History {
-        #[structopt(long, help = "Activate debug logging.")]
-        debug: bool,
+        #[structopt(flatten)]
+        standard_opts: StandardOpts,
     },
     Resume {
         #[structopt(long = "receive-address", parse(try_from_str = parse_monero_address))]
@@ -165,6 +232,22 @@ enum SubCommand {
         #[structopt(short, long)]
         force: bool,
 
+        #[structopt(flatten)]
+        standard_opts: StandardOpts,
+    },
+    ListSellers {
+        #[structopt(
+            long = "rendezvous-point",
+            help = "The multiaddress of the rendezvous point to discover makers through."
+        )]
+        rendezvous_point: Multiaddr,
+
+        #[structopt(
+            long = "namespace",
+            help = "The rendezvous namespace to query, either 'mainnet' or 'testnet'. Defaults to the namespace matching --testnet."
+        )]
+        namespace: Option<XmrBtcNamespace>,
+
         #[structopt(flatten)]
         standard_opts: StandardOpts,
     },
@@ -181,8 +264,157 @@ struct StandardOpts {
 
     #[structopt(long, help = "Activate debug logging.")]
     debug: bool,
+
+    #[structopt(
+        long = "json",
+        help = "Output log messages as newline-delimited JSON instead of human-readable text."
+    )]
+    json: bool,
+
+    #[structopt(long, help = "Swap on Testnet rather than Mainnet.")]
+    testnet: bool,
+
+    #[structopt(
+        long = "data-dir",
+        help = "Provide a custom path to the data directory. Defaults to the OS data directory, namespaced per network so a testnet swap can never touch mainnet state.",
+        parse(from_os_str)
+    )]
+    data_dir: Option<PathBuf>,
+
+    #[structopt(
+        long = "electrum-rpc-url",
+        help = "Provide the Bitcoin Electrum RPC URL to connect to. Defaults to a public server for the selected network."
+    )]
+    electrum_rpc_url: Option<Url>,
+
+    #[structopt(
+        long = "monero-daemon-address",
+        help = "Provide the Monero daemon address to connect to. Defaults to a public node for the selected network.",
+        parse(try_from_str = parse_monero_daemon_address)
+    )]
+    monero_daemon_address: Option<String>,
+
+    #[structopt(
+        long = "bitcoin-target-block",
+        help = "The block target confirmation for Bitcoin transactions to be confirmed. Defaults to 3 on mainnet, 1 on testnet."
+    )]
+    bitcoin_target_block: Option<usize>,
+
+    #[structopt(
+        long = "tor-socks5-port",
+        help = "Your local Tor socks5 proxy port, typically 9050. When set, the swap's libp2p traffic is routed through it, hiding your IP from the maker and allowing /onion3/... addresses to be dialed. list-sellers does not use it and connects to the rendezvous point and makers directly."
+    )]
+    tor_socks5_port: Option<u16>,
+}
+
+/// `StandardOpts` with its network-aware defaults (Electrum URL, Monero
+/// daemon, confirmation target) filled in once the selected network is known.
+struct ResolvedStandardOpts {
+    config: Option<PathBuf>,
+    debug: bool,
+    json: bool,
+    electrum_rpc_url: Url,
+    monero_daemon_address: String,
+    bitcoin_target_block: usize,
+    tor_socks5_port: Option<u16>,
+    env_config: Network,
+    data_dir: PathBuf,
+}
+
+impl ResolvedStandardOpts {
+    fn into_arguments(self, command: Command) -> Arguments {
+        Arguments {
+            config: self.config,
+            debug: self.debug,
+            json: self.json,
+            electrum_rpc_url: self.electrum_rpc_url,
+            monero_daemon_address: self.monero_daemon_address,
+            bitcoin_target_block: self.bitcoin_target_block,
+            tor_socks5_port: self.tor_socks5_port,
+            env_config: self.env_config,
+            data_dir: self.data_dir,
+            command,
+        }
+    }
+}
+
+impl StandardOpts {
+    fn resolve(self) -> Result<ResolvedStandardOpts> {
+        let env_config = if self.testnet {
+            Network::Testnet
+        } else {
+            Network::Mainnet
+        };
+
+        let electrum_rpc_url = match self.electrum_rpc_url {
+            Some(url) => url,
+            None if self.testnet => Url::from_str(DEFAULT_ELECTRUM_RPC_URL_TESTNET)?,
+            None => Url::from_str(DEFAULT_ELECTRUM_RPC_URL)?,
+        };
+
+        let monero_daemon_address = self.monero_daemon_address.unwrap_or_else(|| {
+            if self.testnet {
+                DEFAULT_MONERO_DAEMON_ADDRESS_STAGENET.to_owned()
+            } else {
+                DEFAULT_MONERO_DAEMON_ADDRESS.to_owned()
+            }
+        });
+
+        let bitcoin_target_block = self.bitcoin_target_block.unwrap_or(if self.testnet {
+            DEFAULT_BITCOIN_CONFIRMATION_TARGET_TESTNET
+        } else {
+            DEFAULT_BITCOIN_CONFIRMATION_TARGET
+        });
+
+        let data_dir = match self.data_dir {
+            Some(data_dir) => data_dir,
+            None => default_data_dir(env_config)?,
+        };
+
+        Ok(ResolvedStandardOpts {
+            config: self.config,
+            debug: self.debug,
+            json: self.json,
+            electrum_rpc_url,
+            monero_daemon_address,
+            bitcoin_target_block,
+            tor_socks5_port: self.tor_socks5_port,
+            env_config,
+            data_dir,
+        })
+    }
 }
 
+/// `<OS data dir>/xmr-btc-swap/<mainnet|testnet>`, so a testnet swap never
+/// shares a database or wallet with a mainnet one.
+fn default_data_dir(network: Network) -> Result<PathBuf> {
+    let data_dir = dirs::data_dir().context("Could not find the OS data directory")?;
+
+    Ok(data_dir.join("xmr-btc-swap").join(network.data_dir_name()))
+}
+
+/// An `/onion3/...` address cannot be dialed without routing through Tor, so
+/// fail fast instead of letting the swap hang trying to reach it directly.
+fn ensure_tor_available_for_onion_address(addr: &Multiaddr, tor_socks5_port: Option<u16>) -> Result<()> {
+    let is_onion = addr.iter().any(|p| matches!(p, Protocol::Onion3(_)));
+
+    if is_onion && tor_socks5_port.is_none() {
+        bail!(
+            "{} is an onion address but no --tor-socks5-port was given, so it cannot be dialed",
+            addr
+        );
+    }
+
+    Ok(())
+}
+
+const DEFAULT_ELECTRUM_RPC_URL: &str = "ssl://electrum.blockstream.info:50002";
+const DEFAULT_ELECTRUM_RPC_URL_TESTNET: &str = "ssl://electrum.blockstream.info:60002";
+const DEFAULT_MONERO_DAEMON_ADDRESS: &str = "node.xmr.to:18081";
+const DEFAULT_MONERO_DAEMON_ADDRESS_STAGENET: &str = "monero-stagenet.exan.tech:38081";
+const DEFAULT_BITCOIN_CONFIRMATION_TARGET: usize = 3;
+const DEFAULT_BITCOIN_CONFIRMATION_TARGET_TESTNET: usize = 1;
+
 const DEFAULT_ALICE_PEER_ID: &str = "12D3KooWCdMKjesXMJz1SiZ7HgotrxuqhQJbP5sgBm2BwP1cqThi";
 const DEFAULT_ALICE_MULTIADDR: &str = "/dns4/xmr-btc-asb.coblox.tech/tcp/9876";
 
@@ -194,6 +426,7 @@ struct AliceConnection {
     )]
     alice_peer_id: PeerId,
 
+    // Accepts /onion3/... addresses too; dialing one requires --tor-socks5-port.
     #[structopt(
         long = "connect-addr",
         default_value = DEFAULT_ALICE_MULTIADDR
@@ -201,6 +434,61 @@ struct AliceConnection {
     alice_addr: Multiaddr,
 }
 
+/// The rendezvous namespace makers register themselves under, separate for
+/// mainnet and testnet so a `list-sellers` query never mixes the two up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum XmrBtcNamespace {
+    Mainnet,
+    Testnet,
+}
+
+impl XmrBtcNamespace {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            XmrBtcNamespace::Mainnet => "xmr-btc-swap-mainnet",
+            XmrBtcNamespace::Testnet => "xmr-btc-swap-testnet",
+        }
+    }
+
+    /// The namespace a maker/taker on `network` registers under or queries by
+    /// default, so `list-sellers --testnet` without `--namespace` can never
+    /// silently query mainnet.
+    fn from_network(network: Network) -> Self {
+        match network {
+            Network::Mainnet => XmrBtcNamespace::Mainnet,
+            Network::Testnet => XmrBtcNamespace::Testnet,
+        }
+    }
+}
+
+impl FromStr for XmrBtcNamespace {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "mainnet" => Ok(XmrBtcNamespace::Mainnet),
+            "testnet" => Ok(XmrBtcNamespace::Testnet),
+            other => bail!("unknown namespace '{}', expected 'mainnet' or 'testnet'", other),
+        }
+    }
+}
+
+/// Validates that `s` is a bare `host:port` address, matching the `host:port`
+/// form used by Monero daemon RPC, by parsing it as a schemeless URL and
+/// requiring an explicit port.
+fn parse_monero_daemon_address(s: &str) -> Result<String> {
+    let url = Url::parse(&format!("monero://{}", s))
+        .with_context(|| format!("Failed to parse {} as a host:port address", s))?;
+
+    url.host_str()
+        .zip(url.port())
+        .filter(|_| url.username().is_empty() && url.password().is_none())
+        .filter(|_| url.path().is_empty() && url.query().is_none() && url.fragment().is_none())
+        .with_context(|| format!("{} must be in the form host:port", s))?;
+
+    Ok(s.to_owned())
+}
+
 fn parse_monero_address(s: &str) -> Result<monero::Address> {
     monero::Address::from_str(s).with_context(|| {
         format!(
@@ -231,15 +519,22 @@ mod tests {
 
         let parsed_args = parse_args(args).unwrap();
 
-        assert_eq!(parsed_args, Arguments {
+        assert_eq!(parsed_args, ParseResult::Arguments(Arguments {
             config: None,
             debug: false,
+            json: false,
+            electrum_rpc_url: DEFAULT_ELECTRUM_RPC_URL.parse().unwrap(),
+            monero_daemon_address: DEFAULT_MONERO_DAEMON_ADDRESS.to_owned(),
+            bitcoin_target_block: DEFAULT_BITCOIN_CONFIRMATION_TARGET,
+            tor_socks5_port: None,
+            env_config: Network::Mainnet,
+            data_dir: default_data_dir(Network::Mainnet).unwrap(),
             command: Command::BuyXmr {
                 receive_monero_address: "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a".parse().unwrap(),
                 alice_peer_id: DEFAULT_ALICE_PEER_ID.parse().unwrap(),
                 alice_addr: DEFAULT_ALICE_MULTIADDR.parse().unwrap()
             }
-        })
+        }))
     }
 
     #[test]
@@ -255,15 +550,285 @@ mod tests {
 
         let parsed_args = parse_args(args).unwrap();
 
-        assert_eq!(parsed_args, Arguments {
+        assert_eq!(parsed_args, ParseResult::Arguments(Arguments {
             config: None,
             debug: false,
+            json: false,
+            electrum_rpc_url: DEFAULT_ELECTRUM_RPC_URL.parse().unwrap(),
+            monero_daemon_address: DEFAULT_MONERO_DAEMON_ADDRESS.to_owned(),
+            bitcoin_target_block: DEFAULT_BITCOIN_CONFIRMATION_TARGET,
+            tor_socks5_port: None,
+            env_config: Network::Mainnet,
+            data_dir: default_data_dir(Network::Mainnet).unwrap(),
             command: Command::Resume {
                 receive_monero_address: "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a".parse().unwrap(),
                 swap_id: "6cc8881d-9def-409b-93fc-6c3796f5a777".parse().unwrap(),
                 alice_peer_id: DEFAULT_ALICE_PEER_ID.parse().unwrap(),
                 alice_addr: DEFAULT_ALICE_MULTIADDR.parse().unwrap()
             }
-        })
+        }))
+    }
+
+    #[test]
+    fn given_list_sellers_subcommand_then_discovers_via_rendezvous_point() {
+        let args = vec![
+            BINARY_NAME,
+            "list-sellers",
+            "--rendezvous-point",
+            "/dns4/rendezvous.coblox.tech/tcp/8888",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        assert_eq!(parsed_args, ParseResult::Arguments(Arguments {
+            config: None,
+            debug: false,
+            json: false,
+            electrum_rpc_url: DEFAULT_ELECTRUM_RPC_URL.parse().unwrap(),
+            monero_daemon_address: DEFAULT_MONERO_DAEMON_ADDRESS.to_owned(),
+            bitcoin_target_block: DEFAULT_BITCOIN_CONFIRMATION_TARGET,
+            tor_socks5_port: None,
+            env_config: Network::Mainnet,
+            data_dir: default_data_dir(Network::Mainnet).unwrap(),
+            command: Command::ListSellers {
+                rendezvous_point: "/dns4/rendezvous.coblox.tech/tcp/8888".parse().unwrap(),
+                namespace: XmrBtcNamespace::Mainnet,
+            }
+        }))
+    }
+
+    #[test]
+    fn given_list_sellers_with_testnet_and_no_namespace_then_namespace_defaults_to_testnet() {
+        let args = vec![
+            BINARY_NAME,
+            "list-sellers",
+            "--rendezvous-point",
+            "/dns4/rendezvous.coblox.tech/tcp/8888",
+            "--testnet",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments { command, .. }) => match command {
+                Command::ListSellers { namespace, .. } => {
+                    assert_eq!(namespace, XmrBtcNamespace::Testnet)
+                }
+                other => panic!("expected ListSellers, got {:?}", other),
+            },
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_history_subcommand_then_resolves_like_other_subcommands() {
+        let args = vec![BINARY_NAME, "history", "--testnet"];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments {
+                env_config,
+                data_dir,
+                command,
+                ..
+            }) => {
+                assert_eq!(env_config, Network::Testnet);
+                assert_eq!(data_dir, default_data_dir(Network::Testnet).unwrap());
+                assert_eq!(command, Command::History);
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_json_flag_then_json_output_enabled() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--json",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments { json, .. }) => assert!(json),
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_testnet_flag_then_testnet_defaults_are_used() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--testnet",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments {
+                electrum_rpc_url,
+                monero_daemon_address,
+                bitcoin_target_block,
+                ..
+            }) => {
+                assert_eq!(electrum_rpc_url, DEFAULT_ELECTRUM_RPC_URL_TESTNET.parse().unwrap());
+                assert_eq!(monero_daemon_address, DEFAULT_MONERO_DAEMON_ADDRESS_STAGENET);
+                assert_eq!(bitcoin_target_block, DEFAULT_BITCOIN_CONFIRMATION_TARGET_TESTNET);
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_testnet_flag_then_data_dir_is_namespaced_per_network() {
+        let mainnet_args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+        ];
+        let testnet_args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--testnet",
+        ];
+
+        let mainnet_data_dir = match parse_args(mainnet_args).unwrap() {
+            ParseResult::Arguments(Arguments { env_config, data_dir, .. }) => {
+                assert_eq!(env_config, Network::Mainnet);
+                data_dir
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        };
+        let testnet_data_dir = match parse_args(testnet_args).unwrap() {
+            ParseResult::Arguments(Arguments { env_config, data_dir, .. }) => {
+                assert_eq!(env_config, Network::Testnet);
+                data_dir
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        };
+
+        assert_ne!(mainnet_data_dir, testnet_data_dir);
+    }
+
+    #[test]
+    fn given_explicit_data_dir_then_overrides_default() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--data-dir",
+            "/tmp/my-xmr-btc-swap-data",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments { data_dir, .. }) => {
+                assert_eq!(data_dir, PathBuf::from("/tmp/my-xmr-btc-swap-data"));
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_explicit_monero_daemon_address_then_overrides_default() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--monero-daemon-address",
+            "my-own-node.example:18081",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments { monero_daemon_address, .. }) => {
+                assert_eq!(monero_daemon_address, "my-own-node.example:18081");
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_monero_daemon_address_without_port_then_errors() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--monero-daemon-address",
+            "nonsense",
+        ];
+
+        let result = parse_args(args);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn given_explicit_electrum_rpc_url_then_overrides_default() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--electrum-rpc-url",
+            "ssl://my-own-node.example:50002",
+        ];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        match parsed_args {
+            ParseResult::Arguments(Arguments { electrum_rpc_url, .. }) => {
+                assert_eq!(electrum_rpc_url, "ssl://my-own-node.example:50002".parse().unwrap());
+            }
+            ParseResult::Early(_) => panic!("expected Arguments, got Early"),
+        }
+    }
+
+    #[test]
+    fn given_onion_address_without_tor_port_then_errors() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--connect-addr",
+            "/onion3/vww6ybal4bd7szmgncyruucpgfkqahzddi37ktceo3ah7ngmcopnpyyd:1234",
+        ];
+
+        let result = parse_args(args);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn given_onion_address_with_tor_port_then_succeeds() {
+        let args = vec![
+            BINARY_NAME,
+            "--receive-address",
+            "53gEuGZUhP9JMEBZoGaFNzhwEgiG7hwQdMCqFxiyiTeFPmkbt1mAoNybEUvYBKHcnrSgxnVWgZsTvRBaHBNXPa8tHiCU51a",
+            "--connect-addr",
+            "/onion3/vww6ybal4bd7szmgncyruucpgfkqahzddi37ktceo3ah7ngmcopnpyyd:1234",
+            "--tor-socks5-port",
+            "9050",
+        ];
+
+        let result = parse_args(args);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn given_version_flag_then_early_exit() {
+        let args = vec![BINARY_NAME, "--version"];
+
+        let parsed_args = parse_args(args).unwrap();
+
+        assert!(matches!(parsed_args, ParseResult::Early(_)));
     }
 }
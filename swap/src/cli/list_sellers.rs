@@ -0,0 +1,481 @@
+//! Discover makers registered at a rendezvous point (see `Command::ListSellers`).
+//!
+//! We dial the rendezvous point, issue a `DISCOVER` for the swap namespace,
+//! dedupe the returned registrations by peer id, then briefly connect to
+//! each discovered maker to fetch its current quote before printing the
+//! results as a table, or as newline-delimited JSON when `--json` is set.
+
+use crate::cli::command::XmrBtcNamespace;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::prelude::*;
+use libp2p::multiaddr::Protocol;
+use libp2p::request_response::{
+    ProtocolName, ProtocolSupport, RequestResponse, RequestResponseCodec, RequestResponseEvent,
+    RequestResponseMessage,
+};
+use libp2p::swarm::{NetworkBehaviour, Swarm, SwarmEvent};
+use libp2p::{identity, rendezvous, Multiaddr, PeerId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::time::Duration;
+use tokio::time::error::Elapsed;
+use tokio::time::timeout;
+
+const DISCOVER_TIMEOUT: Duration = Duration::from_secs(10);
+const QUOTE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Wire protocol name for fetching a maker's quote once discovered.
+///
+/// TODO: this is a placeholder request/response format (JSON-over-stream)
+/// and has not been confirmed against the ASB-side quote protocol the
+/// maker binary actually speaks. Until that's verified, every discovered
+/// maker will show up as "unreachable" in practice. Swap this codec out
+/// for whichever protocol/codec the ASB crate already exposes before this
+/// ships.
+const QUOTE_PROTOCOL_NAME: &[u8] = b"/xmr-btc-swap/quote/1.0.0";
+
+/// A maker discovered through a rendezvous point, together with its quoted
+/// spread if it answered the quote request before `QUOTE_TIMEOUT`.
+#[derive(Debug, Clone)]
+pub struct Seller {
+    pub peer_id: PeerId,
+    pub multiaddr: Multiaddr,
+    pub quote: Option<Quote>,
+}
+
+/// The price/min/max a maker quoted for a swap, in satoshis.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Quote {
+    pub price: u64,
+    pub min_quantity: u64,
+    pub max_quantity: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QuoteRequest;
+
+#[derive(Debug, Clone)]
+struct QuoteProtocol;
+
+impl ProtocolName for QuoteProtocol {
+    fn protocol_name(&self) -> &[u8] {
+        QUOTE_PROTOCOL_NAME
+    }
+}
+
+#[derive(Clone)]
+struct QuoteCodec;
+
+#[async_trait]
+impl RequestResponseCodec for QuoteCodec {
+    type Protocol = QuoteProtocol;
+    type Request = QuoteRequest;
+    type Response = Quote;
+
+    async fn read_request<T>(&mut self, _: &QuoteProtocol, _io: &mut T) -> io::Result<QuoteRequest>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        Ok(QuoteRequest)
+    }
+
+    async fn read_response<T>(&mut self, _: &QuoteProtocol, io: &mut T) -> io::Result<Quote>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut buf = Vec::new();
+        io.read_to_end(&mut buf).await?;
+        serde_json::from_slice(&buf).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    async fn write_request<T>(&mut self, _: &QuoteProtocol, io: &mut T, _: QuoteRequest) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.close().await
+    }
+
+    async fn write_response<T>(&mut self, _: &QuoteProtocol, io: &mut T, res: Quote) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        let bytes = serde_json::to_vec(&res).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        io.write_all(&bytes).await?;
+        io.close().await
+    }
+}
+
+#[derive(NetworkBehaviour)]
+#[behaviour(out_event = "ComposedEvent", event_process = false)]
+struct Behaviour {
+    rendezvous: rendezvous::client::Behaviour,
+    quote: RequestResponse<QuoteCodec>,
+}
+
+#[derive(Debug)]
+enum ComposedEvent {
+    Rendezvous(rendezvous::client::Event),
+    Quote(RequestResponseEvent<QuoteRequest, Quote>),
+}
+
+impl From<rendezvous::client::Event> for ComposedEvent {
+    fn from(event: rendezvous::client::Event) -> Self {
+        ComposedEvent::Rendezvous(event)
+    }
+}
+
+impl From<RequestResponseEvent<QuoteRequest, Quote>> for ComposedEvent {
+    fn from(event: RequestResponseEvent<QuoteRequest, Quote>) -> Self {
+        ComposedEvent::Quote(event)
+    }
+}
+
+/// Dial `rendezvous_point`, discover every maker registered under
+/// `namespace`, fetch each one's current quote and print the results as a
+/// table (or as newline-delimited JSON if `json` is set). Prints a friendly
+/// message instead of an empty table when the rendezvous point has no
+/// makers registered, and a distinct message when the rendezvous point
+/// could not be reached at all before `DISCOVER_TIMEOUT` elapsed.
+pub async fn list_sellers(rendezvous_point: Multiaddr, namespace: XmrBtcNamespace, json: bool) -> Result<()> {
+    let rendezvous_peer_id = extract_peer_id(&rendezvous_point)
+        .context("Rendezvous point address must end in /p2p/<peer id>")?;
+
+    let identity = identity::Keypair::generate_ed25519();
+    let local_peer_id = PeerId::from(identity.public());
+
+    let transport = libp2p::tokio_development_transport(identity.clone())
+        .context("Failed to build libp2p transport")?;
+
+    let behaviour = Behaviour {
+        rendezvous: rendezvous::client::Behaviour::new(identity),
+        quote: RequestResponse::new(
+            QuoteCodec,
+            std::iter::once((QuoteProtocol, ProtocolSupport::Full)),
+            Default::default(),
+        ),
+    };
+
+    let mut swarm = Swarm::new(transport, behaviour, local_peer_id);
+    swarm.dial(rendezvous_point.clone())?;
+
+    let outcome = timeout(
+        DISCOVER_TIMEOUT,
+        discover(&mut swarm, rendezvous_peer_id, namespace),
+    )
+    .await;
+
+    let registrations = match discovery_message(&rendezvous_point, namespace, outcome) {
+        Ok(registrations) => registrations,
+        Err(message) => {
+            println!("{}", message);
+            return Ok(());
+        }
+    };
+
+    let mut sellers = Vec::with_capacity(registrations.len());
+    for (peer_id, multiaddr) in registrations {
+        let quote = timeout(QUOTE_TIMEOUT, fetch_quote(&mut swarm, peer_id, multiaddr.clone()))
+            .await
+            .unwrap_or_default();
+        sellers.push(Seller {
+            peer_id,
+            multiaddr,
+            quote,
+        });
+    }
+
+    print_sellers(&sellers, json);
+
+    Ok(())
+}
+
+/// What happened while waiting for the rendezvous point to answer `DISCOVER`.
+enum DiscoverOutcome {
+    /// The rendezvous point answered, possibly with zero registrations.
+    Found(HashMap<PeerId, Multiaddr>),
+    /// The rendezvous point answered with a protocol-level error.
+    Failed(String),
+}
+
+/// Turns a discovery attempt into either the registrations to fetch quotes
+/// for, or a message to show the user, making sure a dead/unreachable
+/// rendezvous point (`Err` from the outer `timeout`) is never confused with
+/// a live one that simply has no sellers registered (`Found` with an empty
+/// map) — both previously produced the exact same "No sellers" message.
+fn discovery_message(
+    rendezvous_point: &Multiaddr,
+    namespace: XmrBtcNamespace,
+    outcome: Result<DiscoverOutcome, Elapsed>,
+) -> Result<HashMap<PeerId, Multiaddr>, String> {
+    match outcome {
+        Ok(DiscoverOutcome::Found(registrations)) if registrations.is_empty() => Err(format!(
+            "No sellers are currently registered at {} under namespace '{}'",
+            rendezvous_point,
+            namespace.as_str()
+        )),
+        Ok(DiscoverOutcome::Found(registrations)) => Ok(registrations),
+        Ok(DiscoverOutcome::Failed(error)) => Err(format!(
+            "Rendezvous discovery at {} failed: {}",
+            rendezvous_point, error
+        )),
+        Err(_) => Err(format!(
+            "Timed out after {}s waiting for {} to respond to discovery; the rendezvous point may be unreachable",
+            DISCOVER_TIMEOUT.as_secs(),
+            rendezvous_point
+        )),
+    }
+}
+
+/// Sends a single `DISCOVER` once connected to the rendezvous point and
+/// collects every registration, deduping multiple addresses per peer down to
+/// the first one seen.
+async fn discover(
+    swarm: &mut Swarm<Behaviour>,
+    rendezvous_peer_id: PeerId,
+    namespace: XmrBtcNamespace,
+) -> DiscoverOutcome {
+    let mut discover_sent = false;
+
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::ConnectionEstablished { peer_id, .. }
+                if peer_id == rendezvous_peer_id && !discover_sent =>
+            {
+                swarm.behaviour_mut().rendezvous.discover(
+                    Some(rendezvous::Namespace::new(namespace.as_str().to_owned()).expect("valid namespace")),
+                    None,
+                    None,
+                    rendezvous_peer_id,
+                );
+                discover_sent = true;
+            }
+            SwarmEvent::Behaviour(ComposedEvent::Rendezvous(rendezvous::client::Event::Discovered {
+                registrations: discovered,
+                ..
+            })) => return DiscoverOutcome::Found(dedupe_registrations(discovered)),
+            SwarmEvent::Behaviour(ComposedEvent::Rendezvous(rendezvous::client::Event::DiscoverFailed {
+                error,
+                ..
+            })) => {
+                tracing::warn!("Rendezvous discovery failed: {:?}", error);
+                return DiscoverOutcome::Failed(format!("{:?}", error));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Collapses a list of rendezvous registrations down to one address per
+/// peer, keeping the first address seen for a given peer id.
+fn dedupe_registrations(discovered: Vec<rendezvous::Registration>) -> HashMap<PeerId, Multiaddr> {
+    let mut registrations = HashMap::new();
+    for registration in discovered {
+        for addr in registration.record.addresses() {
+            registrations
+                .entry(registration.record.peer_id())
+                .or_insert_with(|| addr.clone());
+        }
+    }
+    registrations
+}
+
+/// Connects to a single discovered maker and requests its current quote.
+/// Returns `None` if the maker is unreachable or errors out.
+async fn fetch_quote(swarm: &mut Swarm<Behaviour>, peer_id: PeerId, multiaddr: Multiaddr) -> Option<Quote> {
+    swarm.behaviour_mut().quote.add_address(&peer_id, multiaddr);
+    let request_id = swarm.behaviour_mut().quote.send_request(&peer_id, QuoteRequest);
+
+    loop {
+        match swarm.select_next_some().await {
+            SwarmEvent::Behaviour(ComposedEvent::Quote(RequestResponseEvent::Message {
+                message: RequestResponseMessage::Response { request_id: id, response },
+                ..
+            })) if id == request_id => return Some(response),
+            SwarmEvent::Behaviour(ComposedEvent::Quote(RequestResponseEvent::OutboundFailure {
+                request_id: id,
+                error,
+                ..
+            })) if id == request_id => {
+                tracing::warn!("Failed to fetch quote from {}: {:?}", peer_id, error);
+                return None;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn extract_peer_id(multiaddr: &Multiaddr) -> Option<PeerId> {
+    multiaddr.iter().find_map(|p| match p {
+        Protocol::P2p(hash) => PeerId::from_multihash(hash).ok(),
+        _ => None,
+    })
+}
+
+/// A `Seller` reshaped for newline-delimited JSON output, using plain
+/// strings for the `PeerId`/`Multiaddr` fields instead of relying on their
+/// `Serialize` impls staying wire-compatible with this CLI's JSON output.
+#[derive(Serialize)]
+struct SellerRecord {
+    peer_id: String,
+    multiaddr: String,
+    quote: Option<Quote>,
+}
+
+impl From<&Seller> for SellerRecord {
+    fn from(seller: &Seller) -> Self {
+        SellerRecord {
+            peer_id: seller.peer_id.to_string(),
+            multiaddr: seller.multiaddr.to_string(),
+            quote: seller.quote,
+        }
+    }
+}
+
+fn print_sellers(sellers: &[Seller], json: bool) {
+    if json {
+        for seller in sellers {
+            match serde_json::to_string(&SellerRecord::from(seller)) {
+                Ok(line) => println!("{}", line),
+                Err(e) => tracing::error!("Failed to serialize seller {}: {}", seller.peer_id, e),
+            }
+        }
+        return;
+    }
+
+    println!(
+        "{:<55} {:<45} {:>10} {:>10} {:>10}",
+        "PEER ID", "ADDRESS", "PRICE", "MIN", "MAX"
+    );
+    for seller in sellers {
+        match seller.quote {
+            Some(quote) => println!(
+                "{:<55} {:<45} {:>10} {:>10} {:>10}",
+                seller.peer_id, seller.multiaddr, quote.price, quote.min_quantity, quote.max_quantity
+            ),
+            None => println!(
+                "{:<55} {:<45} {:>10}",
+                seller.peer_id, seller.multiaddr, "unreachable"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registration(keypair: &identity::Keypair, addr: Multiaddr) -> rendezvous::Registration {
+        rendezvous::Registration {
+            namespace: rendezvous::Namespace::new("xmr-btc-swap-mainnet".to_owned()).unwrap(),
+            record: rendezvous::PeerRecord::new(keypair, vec![addr]).unwrap(),
+            ttl: 7200,
+        }
+    }
+
+    #[test]
+    fn given_two_registrations_for_same_peer_then_dedupe_keeps_one_entry() {
+        let keypair = identity::Keypair::generate_ed25519();
+        let first_addr: Multiaddr = "/ip4/127.0.0.1/tcp/1111".parse().unwrap();
+        let second_addr: Multiaddr = "/ip4/127.0.0.1/tcp/2222".parse().unwrap();
+
+        let discovered = vec![
+            registration(&keypair, first_addr.clone()),
+            registration(&keypair, second_addr),
+        ];
+
+        let registrations = dedupe_registrations(discovered);
+
+        assert_eq!(registrations.len(), 1);
+        assert_eq!(
+            registrations.get(&PeerId::from(keypair.public())),
+            Some(&first_addr)
+        );
+    }
+
+    #[test]
+    fn given_two_different_peers_then_dedupe_keeps_both() {
+        let first_keypair = identity::Keypair::generate_ed25519();
+        let second_keypair = identity::Keypair::generate_ed25519();
+        let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1111".parse().unwrap();
+
+        let discovered = vec![
+            registration(&first_keypair, addr.clone()),
+            registration(&second_keypair, addr),
+        ];
+
+        let registrations = dedupe_registrations(discovered);
+
+        assert_eq!(registrations.len(), 2);
+    }
+
+    #[test]
+    fn given_found_with_empty_registrations_then_reports_no_sellers() {
+        let rendezvous_point: Multiaddr = "/dns4/rendezvous.coblox.tech/tcp/8888".parse().unwrap();
+
+        let result = discovery_message(
+            &rendezvous_point,
+            XmrBtcNamespace::Mainnet,
+            Ok(DiscoverOutcome::Found(HashMap::new())),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            format!(
+                "No sellers are currently registered at {} under namespace 'xmr-btc-swap-mainnet'",
+                rendezvous_point
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn given_discover_times_out_then_reports_timeout_not_empty_registrations() {
+        let rendezvous_point: Multiaddr = "/dns4/rendezvous.coblox.tech/tcp/8888".parse().unwrap();
+
+        let outcome = timeout(Duration::from_millis(1), futures::future::pending::<DiscoverOutcome>()).await;
+
+        let result = discovery_message(&rendezvous_point, XmrBtcNamespace::Mainnet, outcome);
+
+        let message = result.unwrap_err();
+        assert!(message.contains("Timed out"), "message was: {}", message);
+        assert!(!message.contains("No sellers"), "message was: {}", message);
+    }
+
+    #[test]
+    fn given_discover_fails_then_reports_failure_not_empty_registrations() {
+        let rendezvous_point: Multiaddr = "/dns4/rendezvous.coblox.tech/tcp/8888".parse().unwrap();
+
+        let result = discovery_message(
+            &rendezvous_point,
+            XmrBtcNamespace::Mainnet,
+            Ok(DiscoverOutcome::Failed("bad namespace".to_owned())),
+        );
+
+        let message = result.unwrap_err();
+        assert!(message.contains("failed"), "message was: {}", message);
+        assert!(!message.contains("No sellers"), "message was: {}", message);
+    }
+
+    #[test]
+    fn given_found_with_registrations_then_returns_them() {
+        let rendezvous_point: Multiaddr = "/dns4/rendezvous.coblox.tech/tcp/8888".parse().unwrap();
+        let keypair = identity::Keypair::generate_ed25519();
+        let addr: Multiaddr = "/ip4/127.0.0.1/tcp/1111".parse().unwrap();
+        let mut registrations = HashMap::new();
+        registrations.insert(PeerId::from(keypair.public()), addr);
+
+        let result = discovery_message(
+            &rendezvous_point,
+            XmrBtcNamespace::Mainnet,
+            Ok(DiscoverOutcome::Found(registrations.clone())),
+        );
+
+        assert_eq!(result.unwrap(), registrations);
+    }
+
+    #[test]
+    fn quote_protocol_name_is_the_expected_wire_identifier() {
+        assert_eq!(QuoteProtocol.protocol_name(), QUOTE_PROTOCOL_NAME);
+    }
+}